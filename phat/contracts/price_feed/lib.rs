@@ -12,7 +12,7 @@ mod price_feed {
 
     use pink_extension::chain_extension::signing;
     use pink_extension::{error, ResultExt};
-    use scale::{Decode, Encode};
+    use scale::{Decode, Encode, Error as CodecError, Input};
 
     use fixed::types::U80F48 as Fp;
 
@@ -22,7 +22,7 @@ mod price_feed {
 
     /// Message to request the price of the trading pair
     /// message pushed in the queue by this contract and read by the offchain rollup
-    #[derive(Encode, Decode)]
+    #[derive(Encode)]
     struct PriceRequestMessage {
         /// id of the pair (use as key in the Mapping)
         trading_pair_id: TradingPairId,
@@ -30,6 +30,27 @@ mod price_feed {
         /// Note: it will be better to not save this data in the storage
         token0: String,
         token1: String,
+        /// Number of decimals the reported `price` should be scaled to.
+        /// Callers that don't care should use 18, the original hardcoded scale.
+        decimals: u8,
+    }
+
+    impl Decode for PriceRequestMessage {
+        /// Hand-rolled so requests pushed to the queue before `decimals`
+        /// existed still decode: if the trailing byte is missing, default to
+        /// 18 (the original hardcoded scale) instead of failing to decode.
+        fn decode<I: Input>(input: &mut I) -> core::result::Result<Self, CodecError> {
+            let trading_pair_id = TradingPairId::decode(input)?;
+            let token0 = String::decode(input)?;
+            let token1 = String::decode(input)?;
+            let decimals = u8::decode(input).unwrap_or(18);
+            Ok(Self {
+                trading_pair_id,
+                token0,
+                token1,
+                decimals,
+            })
+        }
     }
     /// Message sent to provide the price of the trading pair
     /// response pushed in the queue by the offchain rollup and read by this contract
@@ -46,16 +67,186 @@ mod price_feed {
     }
 
     /// Type of response when the offchain rollup communicate with this contract
-    //const TYPE_ERROR: u8 = 0;
+    const TYPE_ERROR: u8 = 0;
     //const TYPE_RESPONSE: u8 = 10;
     const TYPE_FEED: u8 = 11;
 
+    /// Stable error codes carried in `PriceResponseMessage::err_no` when
+    /// `resp_type == TYPE_ERROR`, so the anchor contract can distinguish a
+    /// missing/unresolvable price from a genuine zero.
+    const ERR_NO_UNKNOWN_PAIR: u128 = 1;
+    const ERR_NO_SOURCE_UNAVAILABLE: u128 = 2;
+    const ERR_NO_PARSE_FAILURE: u128 = 3;
+    const ERR_NO_INVALID_DECIMALS: u128 = 4;
+
+    /// What a source found for one pair: a usable quote, or a quote it
+    /// received but couldn't parse (distinct from not carrying the pair at
+    /// all, which is simply absent from the returned map).
+    enum Quote {
+        Found(Fp),
+        ParseFailed,
+    }
+
+    /// A price feed backend that can quote a set of trading pairs.
+    ///
+    /// Implementations must tolerate a pair it cannot quote by simply omitting
+    /// it from the returned map, rather than failing the whole batch. A pair
+    /// whose quote was received but couldn't be parsed as a number is still
+    /// reported, as `Quote::ParseFailed`, so that case isn't mistaken for the
+    /// pair being unsupported by the source.
+    trait PriceSource {
+        fn fetch(&self, pairs: &[PriceRequestMessage]) -> Result<BTreeMap<TradingPairId, Quote>>;
+    }
+
+    /// The price sources this contract knows how to query.
+    ///
+    /// Stored in `Config` so the enabled set and its order survive across calls.
+    #[derive(Encode, Decode, Debug, Clone, Copy, PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum PriceSourceKind {
+        CoinGecko,
+        Binance,
+    }
+
+    impl PriceSource for PriceSourceKind {
+        fn fetch(&self, pairs: &[PriceRequestMessage]) -> Result<BTreeMap<TradingPairId, Quote>> {
+            match self {
+                PriceSourceKind::CoinGecko => CoinGeckoSource.fetch(pairs),
+                PriceSourceKind::Binance => BinanceSource.fetch(pairs),
+            }
+        }
+    }
+
+    struct CoinGeckoSource;
+
+    impl PriceSource for CoinGeckoSource {
+        fn fetch(&self, pairs: &[PriceRequestMessage]) -> Result<BTreeMap<TradingPairId, Quote>> {
+            let quotes = PriceFeed::fetch_coingecko_prices(pairs)?;
+            let mut result = BTreeMap::new();
+            for pair in pairs {
+                if let Some(price) = quotes.get(&pair.token0).and_then(|t| t.get(&pair.token1)) {
+                    let quote = match Fp::from_str(price) {
+                        Ok(fp) => Quote::Found(fp),
+                        Err(_) => Quote::ParseFailed,
+                    };
+                    result.insert(pair.trading_pair_id, quote);
+                }
+            }
+            Ok(result)
+        }
+    }
+
+    /// Maps a CoinGecko asset id, as stored in `PriceRequestMessage::token0`,
+    /// to the base asset ticker Binance quotes it under. CoinGecko ids and
+    /// Binance tickers are different namespaces, so pairs not covered here
+    /// are skipped rather than queried under a guessed symbol.
+    fn binance_base_ticker(coingecko_id: &str) -> Option<&'static str> {
+        match coingecko_id {
+            "bitcoin" => Some("BTC"),
+            "ethereum" => Some("ETH"),
+            "binancecoin" => Some("BNB"),
+            "polkadot" => Some("DOT"),
+            "kusama" => Some("KSM"),
+            "astar" => Some("ASTR"),
+            "moonbeam" => Some("GLMR"),
+            "pha" => Some("PHA"),
+            _ => None,
+        }
+    }
+
+    /// Maps a CoinGecko quote currency id to the Binance quote asset it's
+    /// actually quoted in. Binance has no USD markets, only stablecoin ones.
+    fn binance_quote_ticker(coingecko_id: &str) -> Option<&'static str> {
+        match coingecko_id {
+            "usd" => Some("USDT"),
+            _ => None,
+        }
+    }
+
+    struct BinanceSource;
+
+    impl PriceSource for BinanceSource {
+        fn fetch(&self, pairs: &[PriceRequestMessage]) -> Result<BTreeMap<TradingPairId, Quote>> {
+            let mut result = BTreeMap::new();
+            for pair in pairs {
+                let (Some(base), Some(quote)) = (
+                    binance_base_ticker(&pair.token0),
+                    binance_quote_ticker(&pair.token1),
+                ) else {
+                    continue;
+                };
+                let symbol = format!("{base}{quote}");
+                // <https://binance-docs.github.io/apidocs/spot/en/#symbol-price-ticker>
+                let url = format!("https://api.binance.com/api/v3/ticker/price?symbol={symbol}");
+                let headers = vec![("accept".into(), "application/json".into())];
+                let resp = pink_extension::http_get!(url, headers);
+                if resp.status_code != 200 {
+                    continue;
+                }
+                let parsed: BTreeMap<String, String> = match pink_json::from_slice(&resp.body) {
+                    Ok(parsed) => parsed,
+                    Err(_) => continue,
+                };
+                if let Some(price) = parsed.get("price") {
+                    let quote = match Fp::from_str(price) {
+                        Ok(fp) => Quote::Found(fp),
+                        Err(_) => Quote::ParseFailed,
+                    };
+                    result.insert(pair.trading_pair_id, quote);
+                }
+            }
+            Ok(result)
+        }
+    }
+
+    /// Aggregates quotes collected from the enabled sources for a single pair.
+    ///
+    /// Uses the median when at least 3 sources answered (the lower of the two
+    /// middle quotes for an even count), the mean of the two quotes when
+    /// exactly 2 answered, and the lone quote otherwise.
+    fn aggregate_quotes(mut quotes: Vec<Fp>) -> Option<Fp> {
+        quotes.sort();
+        match quotes.len() {
+            0 => None,
+            1 => Some(quotes[0]),
+            2 => Some((quotes[0] + quotes[1]) / Fp::from_num(2u8)),
+            len => Some(quotes[(len - 1) / 2]),
+        }
+    }
+
+    /// `U80F48` has only 80 integer bits (~1.2e24, i.e. ~10^24.08), not 10^38.
+    /// Cap `decimals` well below that ceiling to leave headroom for the
+    /// quoted price's own magnitude once it's multiplied by the scaling
+    /// factor below.
+    const MAX_PRICE_DECIMALS: u8 = 18;
+
+    /// Computes the `10^decimals` scaling factor for a reported price.
+    ///
+    /// Returns `Err` when `decimals` exceeds `MAX_PRICE_DECIMALS`, or when the
+    /// resulting factor doesn't fit in `Fp` (defense in depth against the
+    /// same overflow `MAX_PRICE_DECIMALS` is meant to prevent).
+    fn decimals_multiplier(decimals: u8) -> Result<Fp> {
+        if decimals > MAX_PRICE_DECIMALS {
+            return Err(Error::InvalidRequest);
+        }
+        Fp::checked_from_num(10u128.pow(decimals as u32)).ok_or(Error::InvalidRequest)
+    }
+
     #[ink(storage)]
     pub struct PriceFeed {
         owner: AccountId,
         config: Option<Config>,
         /// Key for signing the rollup tx.
         attest_key: [u8; 32],
+        /// Last price submitted on-chain per pair, as (price, block number).
+        /// Used to decide whether a fresh quote is worth a new rollup tx.
+        last_submitted: BTreeMap<TradingPairId, (u128, u64)>,
+        /// Accounts allowed to call `feed_prices`, distinct from the admin
+        /// (`owner`) who configures the rollup target and manages this set.
+        feeders: BTreeMap<AccountId, ()>,
     }
 
     #[derive(Encode, Decode, Debug)]
@@ -72,9 +263,17 @@ mod price_feed {
         contract_id: ContractId,
         /// Key for sending out the rollup meta-tx. None to fallback to the wallet based auth.
         sender_key: Option<[u8; 32]>,
+        /// Enabled price sources, in query order.
+        sources: Vec<PriceSourceKind>,
+        /// Minimum price move (in basis points) required to submit an update
+        /// ahead of the heartbeat.
+        deviation_bps: u32,
+        /// Maximum number of blocks a pair may go without an update, even if
+        /// its price hasn't moved enough to cross `deviation_bps`.
+        heartbeat_blocks: u64,
     }
 
-    #[derive(Encode, Decode, Debug)]
+    #[derive(Encode, Decode, Debug, PartialEq, Eq)]
     #[repr(u8)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub enum Error {
@@ -111,10 +310,17 @@ mod price_feed {
         pub fn default() -> Self {
             const NONCE: &[u8] = b"attest_key";
             let private_key = signing::derive_sr25519_key(NONCE);
+            let owner = Self::env().caller();
+            let mut feeders = BTreeMap::new();
+            // The deployer is a feeder by default so the contract is usable
+            // out of the box; grant more feeders with `grant_feeder`.
+            feeders.insert(owner, ());
             Self {
-                owner: Self::env().caller(),
+                owner,
                 attest_key: private_key[..32].try_into().expect("Invalid Key Length"),
                 config: None,
+                last_submitted: BTreeMap::new(),
+                feeders,
             }
         }
 
@@ -145,6 +351,7 @@ mod price_feed {
         /// For dev purpose.
         #[ink(message)]
         pub fn set_attest_key(&mut self, attest_key: Option<Vec<u8>>) -> Result<()> {
+            self.ensure_owner()?;
             self.attest_key = match attest_key {
                 Some(key) => key.try_into().or(Err(Error::InvalidKeyLength))?,
                 None => {
@@ -188,6 +395,17 @@ mod price_feed {
             sender_key: Option<Vec<u8>>,
         ) -> Result<()> {
             self.ensure_owner()?;
+            // Reconfiguring the rollup target (e.g. rotating `sender_key`)
+            // must not reset the sources/thresholds managed by the other
+            // config messages, so carry them over from the existing config.
+            let (sources, deviation_bps, heartbeat_blocks) = match &self.config {
+                Some(existing) => (
+                    existing.sources.clone(),
+                    existing.deviation_bps,
+                    existing.heartbeat_blocks,
+                ),
+                None => (vec![PriceSourceKind::CoinGecko], 0, u64::MAX),
+            };
             self.config = Some(Config {
                 rpc,
                 pallet_id,
@@ -199,10 +417,31 @@ mod price_feed {
                     Some(key) => Some(key.try_into().or(Err(Error::InvalidKeyLength))?),
                     None => None,
                 },
+                sources,
+                deviation_bps,
+                heartbeat_blocks,
             });
             Ok(())
         }
 
+        /// Sets the deviation/heartbeat thresholds that gate a rollup reply (admin only)
+        ///
+        /// A pair is only replied to when its price has moved by at least
+        /// `deviation_bps` (basis points) since the last submitted value, or
+        /// `heartbeat_blocks` have elapsed since then, whichever comes first.
+        #[ink(message)]
+        pub fn set_update_thresholds(
+            &mut self,
+            deviation_bps: u32,
+            heartbeat_blocks: u64,
+        ) -> Result<()> {
+            self.ensure_owner()?;
+            let config = self.config.as_mut().ok_or(Error::NotConfigured)?;
+            config.deviation_bps = deviation_bps;
+            config.heartbeat_blocks = heartbeat_blocks;
+            Ok(())
+        }
+
         /// Transfers the ownership of the contract (admin only)
         #[ink(message)]
         pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<()> {
@@ -211,6 +450,63 @@ mod price_feed {
             Ok(())
         }
 
+        /// Grants the feeder role, allowing the account to call `feed_prices` (admin only)
+        #[ink(message)]
+        pub fn grant_feeder(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            self.feeders.insert(account, ());
+            Ok(())
+        }
+
+        /// Revokes the feeder role from an account (admin only)
+        #[ink(message)]
+        pub fn revoke_feeder(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_owner()?;
+            self.feeders.remove(&account);
+            Ok(())
+        }
+
+        /// Returns whether the given account holds the feeder role
+        #[ink(message)]
+        pub fn has_role(&self, account: AccountId) -> bool {
+            self.feeders.contains_key(&account)
+        }
+
+        /// Gets the enabled price sources, in query order
+        #[ink(message)]
+        pub fn get_price_sources(&self) -> Result<Vec<PriceSourceKind>> {
+            Ok(self.ensure_configured()?.sources.clone())
+        }
+
+        /// Adds a price source to the end of the query order (admin only)
+        #[ink(message)]
+        pub fn add_price_source(&mut self, source: PriceSourceKind) -> Result<()> {
+            self.ensure_owner()?;
+            let config = self.config.as_mut().ok_or(Error::NotConfigured)?;
+            if !config.sources.contains(&source) {
+                config.sources.push(source);
+            }
+            Ok(())
+        }
+
+        /// Removes a price source (admin only)
+        #[ink(message)]
+        pub fn remove_price_source(&mut self, source: PriceSourceKind) -> Result<()> {
+            self.ensure_owner()?;
+            let config = self.config.as_mut().ok_or(Error::NotConfigured)?;
+            config.sources.retain(|s| *s != source);
+            Ok(())
+        }
+
+        /// Replaces the enabled price sources and their query order (admin only)
+        #[ink(message)]
+        pub fn reorder_price_sources(&mut self, sources: Vec<PriceSourceKind>) -> Result<()> {
+            self.ensure_owner()?;
+            let config = self.config.as_mut().ok_or(Error::NotConfigured)?;
+            config.sources = sources;
+            Ok(())
+        }
+
         fn fetch_coingecko_prices(
             trading_pairs: &[PriceRequestMessage],
         ) -> Result<BTreeMap<String, BTreeMap<String, String>>> {
@@ -261,42 +557,173 @@ mod price_feed {
             Ok(parsed)
         }
 
-        /// Processes a price request by a rollup transaction
+        /// Processes the pending price requests sitting in the rollup queue.
+        ///
+        /// Drains the whole queue first, then queries each enabled source once
+        /// for the entire batch (so a source that supports it, like CoinGecko's
+        /// comma-joined ids, is hit with a single HTTP call regardless of how
+        /// many pairs are queued) and builds a `PriceResponseMessage` per
+        /// request, keyed by its `trading_pair_id`. Error responses are always
+        /// replied; a resolved price is replied only when `is_update_due`
+        /// holds for it, and is skipped otherwise. The queue cursor is advanced
+        /// as part of the rollup transaction, so a request is never answered
+        /// twice.
+        ///
+        /// Restricted to accounts holding the feeder role (see `grant_feeder`).
         #[ink(message)]
-        pub fn feed_prices(&self) -> Result<Option<Vec<u8>>> {
-            let config = self.ensure_configured()?;
+        pub fn feed_prices(&mut self) -> Result<Option<Vec<u8>>> {
+            self.ensure_feeder()?;
+            let config = self.config.as_ref().ok_or(Error::NotConfigured)?;
             let mut client = connect(config)?;
+            let current_block = self.env().block_number() as u64;
+
+            let mut requests = Vec::new();
+            while let Some(raw_request) = client
+                .pop()
+                .log_err("failed to pop the request queue")
+                .or(Err(Error::FailedToCallRollup))?
+            {
+                let request: PriceRequestMessage = Decode::decode(&mut raw_request.as_slice())
+                    .log_err("failed to decode the request")
+                    .or(Err(Error::FailedToDecode))?;
+                requests.push(request);
+            }
 
-            // get all trading pairs
-            let trading_pairs = get_trading_pairs();
+            if requests.is_empty() {
+                return Err(Error::NoRequestInQueue);
+            }
+
+            let mut updates = Vec::new();
+            for payload in Self::build_responses(&config.sources, &requests) {
+                match payload.price {
+                    // A resolved price is still subject to the deviation/heartbeat gate.
+                    Some(price)
+                        if Self::is_update_due(
+                            self.last_submitted.get(&payload.trading_pair_id),
+                            price,
+                            current_block,
+                            config.deviation_bps,
+                            config.heartbeat_blocks,
+                        ) =>
+                    {
+                        updates.push((payload.trading_pair_id, price, current_block));
+                        client.action(Action::Reply(payload.encode()));
+                    }
+                    Some(_) => {}
+                    // Errors are always reported so the anchor isn't left guessing.
+                    None => client.action(Action::Reply(payload.encode())),
+                }
+            }
 
-            // fetch the price for this trading pair
-            let prices = Self::fetch_coingecko_prices(&trading_pairs)?;
-
-            // iter on all trading pairs
-            for request in trading_pairs.iter() {
-                if let Some(price) = prices
-                    .get(&request.token0)
-                    .and_then(|t| t.get(&request.token1))
-                {
-                    let fp = Fp::from_str(price)
-                        .log_err("failed to parse real number")
-                        .or(Err(Error::FailedToDecode))?;
-                    let f = fp * Fp::from_num(1_000_000_000_000_000_000u128);
-
-                    // build the payload
-                    let payload = PriceResponseMessage {
+            let sender_key = config.sender_key;
+            // submit the transaction
+            let tx_id = maybe_submit_tx(client, &self.attest_key, sender_key.as_ref())?;
+
+            for (trading_pair_id, price, block) in updates {
+                self.last_submitted.insert(trading_pair_id, (price, block));
+            }
+
+            Ok(tx_id)
+        }
+
+        /// Returns whether a pair's price has moved enough or gone stale enough
+        /// to justify a rollup reply, given the last value we actually submitted.
+        fn is_update_due(
+            last: Option<&(u128, u64)>,
+            new_price: u128,
+            current_block: u64,
+            deviation_bps: u32,
+            heartbeat_blocks: u64,
+        ) -> bool {
+            let Some(&(last_price, last_block)) = last else {
+                return true;
+            };
+            if current_block.saturating_sub(last_block) >= heartbeat_blocks {
+                return true;
+            }
+            if last_price == 0 {
+                return new_price != 0;
+            }
+            let diff = new_price.abs_diff(last_price);
+            diff.saturating_mul(10_000) / last_price >= deviation_bps as u128
+        }
+
+        /// Queries every enabled source once for the whole batch of pending
+        /// requests and aggregates the quotes into a reply payload per pair.
+        ///
+        /// Sources are queried a single time for all of `requests` (rather
+        /// than once per pair) so a source that supports it, like CoinGecko's
+        /// comma-joined ids, only costs one HTTP call regardless of how many
+        /// pairs are queued. Sources that error are skipped rather than
+        /// failing the whole batch, but a quote a source couldn't parse is
+        /// tracked separately so it isn't mistaken for the pair being unknown.
+        /// When no price can be resolved at all, or the result can't be
+        /// scaled to the requested decimals, builds a `TYPE_ERROR` reply
+        /// instead of silently dropping the pair.
+        fn build_responses(
+            sources: &[PriceSourceKind],
+            requests: &[PriceRequestMessage],
+        ) -> Vec<PriceResponseMessage> {
+            let mut quotes: BTreeMap<TradingPairId, Vec<Fp>> = BTreeMap::new();
+            let mut parse_failed: BTreeMap<TradingPairId, ()> = BTreeMap::new();
+            let mut any_source_ok = false;
+            for source in sources {
+                match source.fetch(requests) {
+                    Ok(results) => {
+                        any_source_ok = true;
+                        for (trading_pair_id, quote) in results {
+                            match quote {
+                                Quote::Found(price) => {
+                                    quotes.entry(trading_pair_id).or_default().push(price)
+                                }
+                                Quote::ParseFailed => {
+                                    parse_failed.insert(trading_pair_id, ());
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => error!("price source failed: {:?}", e),
+                }
+            }
+
+            requests
+                .iter()
+                .map(|request| {
+                    let error_response = |err_no| PriceResponseMessage {
+                        resp_type: TYPE_ERROR,
+                        trading_pair_id: request.trading_pair_id,
+                        price: None,
+                        err_no: Some(err_no),
+                    };
+
+                    let pair_quotes = quotes.remove(&request.trading_pair_id).unwrap_or_default();
+                    let Some(fp) = aggregate_quotes(pair_quotes) else {
+                        let err_no = if parse_failed.contains_key(&request.trading_pair_id) {
+                            ERR_NO_PARSE_FAILURE
+                        } else if any_source_ok {
+                            ERR_NO_UNKNOWN_PAIR
+                        } else {
+                            ERR_NO_SOURCE_UNAVAILABLE
+                        };
+                        return error_response(err_no);
+                    };
+
+                    let Ok(multiplier) = decimals_multiplier(request.decimals) else {
+                        return error_response(ERR_NO_INVALID_DECIMALS);
+                    };
+                    let Some(f) = fp.checked_mul(multiplier).and_then(|f| f.checked_round())
+                    else {
+                        return error_response(ERR_NO_INVALID_DECIMALS);
+                    };
+
+                    PriceResponseMessage {
                         resp_type: TYPE_FEED,
                         trading_pair_id: request.trading_pair_id,
                         price: Some(f.to_num()),
                         err_no: None,
-                    };
-                    // Attach the action to the transaction
-                    client.action(Action::Reply(payload.encode()));
-                }
-            }
-            // submit the transaction
-            maybe_submit_tx(client, &self.attest_key, config.sender_key.as_ref())
+                    }
+                })
+                .collect()
         }
 
         /// Returns BadOrigin error if the caller is not the owner
@@ -312,56 +739,15 @@ mod price_feed {
         fn ensure_configured(&self) -> Result<&Config> {
             self.config.as_ref().ok_or(Error::NotConfigured)
         }
-    }
 
-    fn get_trading_pairs() -> Vec<PriceRequestMessage> {
-        vec![
-            PriceRequestMessage {
-                trading_pair_id: 1,
-                token0: "bitcoin".to_string(),
-                token1: "usd".to_string(),
-            },
-            PriceRequestMessage {
-                trading_pair_id: 2,
-                token0: "ethereum".to_string(),
-                token1: "usd".to_string(),
-            },
-            PriceRequestMessage {
-                trading_pair_id: 3,
-                token0: "binancecoin".to_string(),
-                token1: "usd".to_string(),
-            },
-            PriceRequestMessage {
-                trading_pair_id: 13,
-                token0: "polkadot".to_string(),
-                token1: "usd".to_string(),
-            },
-            PriceRequestMessage {
-                trading_pair_id: 171,
-                token0: "kusama".to_string(),
-                token1: "usd".to_string(),
-            },
-            PriceRequestMessage {
-                trading_pair_id: 147,
-                token0: "astar".to_string(),
-                token1: "usd".to_string(),
-            },
-            PriceRequestMessage {
-                trading_pair_id: 720,
-                token0: "shiden".to_string(),
-                token1: "usd".to_string(),
-            },
-            PriceRequestMessage {
-                trading_pair_id: 190,
-                token0: "moonbeam".to_string(),
-                token1: "usd".to_string(),
-            },
-            PriceRequestMessage {
-                trading_pair_id: 384,
-                token0: "pha".to_string(),
-                token1: "usd".to_string(),
-            },
-        ]
+        /// Returns BadOrigin error if the caller doesn't hold the feeder role
+        fn ensure_feeder(&self) -> Result<()> {
+            if self.feeders.contains_key(&self.env().caller()) {
+                Ok(())
+            } else {
+                Err(Error::BadOrigin)
+            }
+        }
     }
 
     fn connect(config: &Config) -> Result<InkRollupClient> {
@@ -415,6 +801,65 @@ mod price_feed {
 
         use super::*;
 
+        fn get_trading_pairs() -> Vec<PriceRequestMessage> {
+            vec![
+                PriceRequestMessage {
+                    trading_pair_id: 1,
+                    token0: "bitcoin".to_string(),
+                    token1: "usd".to_string(),
+                    decimals: 18,
+                },
+                PriceRequestMessage {
+                    trading_pair_id: 2,
+                    token0: "ethereum".to_string(),
+                    token1: "usd".to_string(),
+                    decimals: 18,
+                },
+                PriceRequestMessage {
+                    trading_pair_id: 3,
+                    token0: "binancecoin".to_string(),
+                    token1: "usd".to_string(),
+                    decimals: 18,
+                },
+                PriceRequestMessage {
+                    trading_pair_id: 13,
+                    token0: "polkadot".to_string(),
+                    token1: "usd".to_string(),
+                    decimals: 18,
+                },
+                PriceRequestMessage {
+                    trading_pair_id: 171,
+                    token0: "kusama".to_string(),
+                    token1: "usd".to_string(),
+                    decimals: 18,
+                },
+                PriceRequestMessage {
+                    trading_pair_id: 147,
+                    token0: "astar".to_string(),
+                    token1: "usd".to_string(),
+                    decimals: 18,
+                },
+                PriceRequestMessage {
+                    trading_pair_id: 720,
+                    token0: "shiden".to_string(),
+                    token1: "usd".to_string(),
+                    decimals: 18,
+                },
+                PriceRequestMessage {
+                    trading_pair_id: 190,
+                    token0: "moonbeam".to_string(),
+                    token1: "usd".to_string(),
+                    decimals: 18,
+                },
+                PriceRequestMessage {
+                    trading_pair_id: 384,
+                    token0: "pha".to_string(),
+                    token1: "usd".to_string(),
+                    decimals: 18,
+                },
+            ]
+        }
+
         struct EnvVars {
             /// The RPC endpoint of the target blockchain
             rpc: String,
@@ -483,6 +928,37 @@ mod price_feed {
             assert_eq!(initial_attestor_address, attestor_address);
         }
 
+        #[ink::test]
+        fn grant_and_revoke_feeder_role() {
+            let _ = env_logger::try_init();
+            pink_extension_runtime::mock_ext::mock_all_ext();
+
+            let accounts = ink::env::test::default_accounts::<pink_extension::PinkEnvironment>();
+            let mut price_feed = PriceFeed::default();
+
+            assert!(!price_feed.has_role(accounts.bob));
+
+            price_feed.grant_feeder(accounts.bob).unwrap();
+            assert!(price_feed.has_role(accounts.bob));
+
+            price_feed.revoke_feeder(accounts.bob).unwrap();
+            assert!(!price_feed.has_role(accounts.bob));
+        }
+
+        #[ink::test]
+        fn feed_prices_rejects_a_non_feeder() {
+            let _ = env_logger::try_init();
+            pink_extension_runtime::mock_ext::mock_all_ext();
+
+            let accounts = ink::env::test::default_accounts::<pink_extension::PinkEnvironment>();
+            // Deployed by the default caller (Alice), who is auto-granted the
+            // feeder role; Bob was never granted it.
+            let mut price_feed = PriceFeed::default();
+            ink::env::test::set_caller::<pink_extension::PinkEnvironment>(accounts.bob);
+
+            assert_eq!(price_feed.feed_prices(), Err(Error::BadOrigin));
+        }
+
         fn init_contract() -> PriceFeed {
             let EnvVars {
                 rpc,
@@ -518,13 +994,144 @@ mod price_feed {
             }
         }
 
+        #[ink::test]
+        fn aggregates_quotes_from_coingecko_and_binance() {
+            let _ = env_logger::try_init();
+            pink_extension_runtime::mock_ext::mock_all_ext();
+
+            // bitcoin/usd is quoted by both sources (BTCUSDT on Binance), so
+            // this actually exercises the median-of-two-becomes-mean path in
+            // `aggregate_quotes` against two live, independent APIs instead of
+            // one source always going unused.
+            let request = PriceRequestMessage {
+                trading_pair_id: 1,
+                token0: "bitcoin".to_string(),
+                token1: "usd".to_string(),
+                decimals: 18,
+            };
+
+            let responses = PriceFeed::build_responses(
+                &[PriceSourceKind::CoinGecko, PriceSourceKind::Binance],
+                &[request],
+            );
+
+            assert_eq!(responses.len(), 1);
+            assert_eq!(responses[0].resp_type, TYPE_FEED);
+            assert!(responses[0].price.is_some());
+        }
+
+        #[test]
+        fn is_update_due_when_never_submitted() {
+            assert!(PriceFeed::is_update_due(None, 100, 0, 100, 1_000));
+        }
+
+        #[test]
+        fn is_update_due_on_heartbeat() {
+            // last update was 1_000 blocks ago, matching the heartbeat, even
+            // though the price hasn't moved at all.
+            assert!(PriceFeed::is_update_due(
+                Some(&(100, 0)),
+                100,
+                1_000,
+                10_000,
+                1_000
+            ));
+        }
+
+        #[test]
+        fn is_update_due_on_deviation() {
+            // +20% move clears a 10% (1_000 bps) threshold well before the heartbeat.
+            assert!(PriceFeed::is_update_due(
+                Some(&(100, 0)),
+                120,
+                1,
+                1_000,
+                1_000_000
+            ));
+        }
+
+        #[test]
+        fn is_update_due_skips_a_small_move_before_the_heartbeat() {
+            // +1% move doesn't clear a 10% (1_000 bps) threshold, and we're
+            // nowhere near the heartbeat yet.
+            assert!(!PriceFeed::is_update_due(
+                Some(&(100, 0)),
+                101,
+                1,
+                1_000,
+                1_000_000
+            ));
+        }
+
+        #[test]
+        fn is_update_due_treats_any_move_off_zero_as_due() {
+            assert!(PriceFeed::is_update_due(
+                Some(&(0, 0)),
+                1,
+                1,
+                1_000,
+                1_000_000
+            ));
+        }
+
+        #[test]
+        fn aggregate_quotes_picks_the_median_of_three_or_more() {
+            let quotes = vec![
+                Fp::from_num(30u8),
+                Fp::from_num(10u8),
+                Fp::from_num(20u8),
+                Fp::from_num(40u8),
+            ];
+            assert_eq!(aggregate_quotes(quotes), Some(Fp::from_num(20u8)));
+        }
+
+        #[test]
+        fn aggregate_quotes_averages_exactly_two() {
+            let quotes = vec![Fp::from_num(10u8), Fp::from_num(20u8)];
+            assert_eq!(aggregate_quotes(quotes), Some(Fp::from_num(15u8)));
+        }
+
+        #[test]
+        fn aggregate_quotes_passes_through_a_single_quote() {
+            let quotes = vec![Fp::from_num(42u8)];
+            assert_eq!(aggregate_quotes(quotes), Some(Fp::from_num(42u8)));
+        }
+
+        #[test]
+        fn aggregate_quotes_is_none_when_empty() {
+            assert_eq!(aggregate_quotes(Vec::new()), None);
+        }
+
+        #[test]
+        fn decimals_multiplier_scales_a_known_quote() {
+            let fp = Fp::from_str("1.234567").unwrap();
+            let multiplier = decimals_multiplier(6).unwrap();
+            let f = fp * multiplier;
+            let price: u128 = f.round().to_num();
+
+            assert_eq!(price, 1_234_567);
+        }
+
+        #[test]
+        fn decimals_multiplier_accepts_the_max_decimals() {
+            assert!(decimals_multiplier(MAX_PRICE_DECIMALS).is_ok());
+        }
+
+        #[test]
+        fn decimals_multiplier_rejects_out_of_range() {
+            assert_eq!(
+                decimals_multiplier(MAX_PRICE_DECIMALS + 1),
+                Err(Error::InvalidRequest)
+            );
+        }
+
         #[ink::test]
         #[ignore = "the target contract must be deployed in local node or shibuya"]
         fn feed_prices() {
             let _ = env_logger::try_init();
             pink_extension_runtime::mock_ext::mock_all_ext();
 
-            let price_feed = init_contract();
+            let mut price_feed = init_contract();
 
             let r = price_feed.feed_prices().expect("failed to feed prices");
             debug_println!("answer price: {r:?}");